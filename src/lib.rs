@@ -154,6 +154,158 @@ impl ColorPair {
     fn fgbg_pairid(fg: i16, bg: i16) -> i16 {
         1 + (8 * fg + bg)
     }
+
+    /// The inverse of `fgbg_pairid`: recovers the foreground/background `i16`
+    /// values that were packed into a pair id.
+    fn fgbg_from_pairid(pair_id: i16) -> (i16, i16) {
+        let n = pair_id - 1;
+        (n / 8, n % 8)
+    }
+}
+
+#[cfg(test)]
+mod color_pair_tests {
+    use super::*;
+
+    #[test]
+    fn test_fgbg_pairid_round_trip() {
+        use Color::*;
+        let colors = [Black, Red, Green, Yellow, Blue, Magenta, Cyan, White];
+        for &fg in colors.iter() {
+            for &bg in colors.iter() {
+                let pair_id = ColorPair::fgbg_pairid(color_to_i16(fg), color_to_i16(bg));
+                let (fgi, bgi) = ColorPair::fgbg_from_pairid(pair_id);
+                assert_eq!(i16_to_color(fgi).unwrap(), fg);
+                assert_eq!(i16_to_color(bgi).unwrap(), bg);
+            }
+        }
+    }
+}
+
+/// A builder for picking which mouse events you want `get_input` to report.
+///
+/// Start from `MouseMask::new()` (or `Default::default()`) and chain on
+/// whichever `with_*` methods describe the events you care about, then pass
+/// the result to `set_mouse_mask`. The typical flow is: set the mask once
+/// during setup, then loop on `get_input`, and whenever you see
+/// `Input::KeyMouse` call `get_mouse_event` to decode what actually happened.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MouseMask(pancurses::mmask_t);
+
+impl MouseMask {
+    /// Makes a new, empty mask that reports no mouse events at all.
+    pub fn new() -> Self {
+        MouseMask(0)
+    }
+
+    fn with_flag(self, flag: pancurses::mmask_t) -> Self {
+        MouseMask(self.0 | flag)
+    }
+
+    /// Reports presses of the left mouse button.
+    pub fn with_left_press(self) -> Self {
+        self.with_flag(pancurses::BUTTON1_PRESSED)
+    }
+
+    /// Reports releases of the left mouse button.
+    pub fn with_left_release(self) -> Self {
+        self.with_flag(pancurses::BUTTON1_RELEASED)
+    }
+
+    /// Reports clicks (press followed by release) of the left mouse button.
+    pub fn with_left_click(self) -> Self {
+        self.with_flag(pancurses::BUTTON1_CLICKED)
+    }
+
+    /// Reports double-clicks of the left mouse button.
+    pub fn with_left_double_click(self) -> Self {
+        self.with_flag(pancurses::BUTTON1_DOUBLE_CLICKED)
+    }
+
+    /// Reports presses of the middle mouse button.
+    pub fn with_middle_press(self) -> Self {
+        self.with_flag(pancurses::BUTTON2_PRESSED)
+    }
+
+    /// Reports releases of the middle mouse button.
+    pub fn with_middle_release(self) -> Self {
+        self.with_flag(pancurses::BUTTON2_RELEASED)
+    }
+
+    /// Reports clicks (press followed by release) of the middle mouse button.
+    pub fn with_middle_click(self) -> Self {
+        self.with_flag(pancurses::BUTTON2_CLICKED)
+    }
+
+    /// Reports double-clicks of the middle mouse button.
+    pub fn with_middle_double_click(self) -> Self {
+        self.with_flag(pancurses::BUTTON2_DOUBLE_CLICKED)
+    }
+
+    /// Reports presses of the right mouse button.
+    pub fn with_right_press(self) -> Self {
+        self.with_flag(pancurses::BUTTON3_PRESSED)
+    }
+
+    /// Reports releases of the right mouse button.
+    pub fn with_right_release(self) -> Self {
+        self.with_flag(pancurses::BUTTON3_RELEASED)
+    }
+
+    /// Reports clicks (press followed by release) of the right mouse button.
+    pub fn with_right_click(self) -> Self {
+        self.with_flag(pancurses::BUTTON3_CLICKED)
+    }
+
+    /// Reports double-clicks of the right mouse button.
+    pub fn with_right_double_click(self) -> Self {
+        self.with_flag(pancurses::BUTTON3_DOUBLE_CLICKED)
+    }
+
+    /// Reports mouse movement, independent of any button state, if the
+    /// terminal is able to provide it.
+    pub fn with_motion(self) -> Self {
+        self.with_flag(pancurses::REPORT_MOUSE_POSITION)
+    }
+
+    /// Reports every mouse event that pancurses knows how to report. Simpler
+    /// than listing out every button you care about, at the cost of possibly
+    /// getting events you then have to ignore.
+    pub fn with_all_events(self) -> Self {
+        self.with_flag(pancurses::ALL_MOUSE_EVENTS)
+    }
+}
+
+/// A single decoded mouse event, as returned by `get_mouse_event`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent {
+    /// The column the event happened at.
+    pub x: i32,
+    /// The row the event happened at.
+    pub y: i32,
+    /// Reserved for bezel/extension events on some platforms. Usually 0.
+    pub z: i32,
+    /// Which buttons and/or motions this event represents. Test this against
+    /// the same flags you used to build your `MouseMask`, e.g.
+    /// `pancurses::BUTTON1_CLICKED`.
+    pub button_state: pancurses::mmask_t,
+}
+
+/// A snapshot of which text attributes are currently active, as returned by
+/// `get_attributes`. Useful for saving the current style before drawing
+/// something else and then restoring it afterward via `set_attributes`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Attributes {
+    /// Bold / bright text.
+    pub bold: bool,
+    /// Underlined text.
+    pub underline: bool,
+    /// Reverse video (foreground and background swapped).
+    pub reverse: bool,
+    /// Blinking text.
+    pub blink: bool,
+    /// Dim / half-bright text.
+    pub dim: bool,
 }
 
 /// Converts a `pancurses::OK` value into `true`, and all other values into
@@ -214,6 +366,10 @@ pub struct EasyCurses {
     /// to touch this field at all.
     pub win: pancurses::Window,
     color_support: bool,
+    /// If `get_input` should automatically handle `Input::KeyResize` by
+    /// resizing the window to match the terminal before returning the event.
+    /// Defaults to `true`. See `get_input` for details.
+    pub auto_resize: bool,
 }
 
 impl Drop for EasyCurses {
@@ -254,6 +410,7 @@ impl EasyCurses {
         EasyCurses {
             win: w,
             color_support: color_support,
+            auto_resize: true,
         }
     }
 
@@ -319,13 +476,60 @@ impl EasyCurses {
         })
     }
 
-    // TODO: pancurses::resize_term?
+    /// Resizes the terminal to the given number of rows and columns, via
+    /// [resize_term](http://invisible-island.net/ncurses/man/curs_sp_funcs.3x.html#h3-resizeterm-resize_term).
+    /// On most platforms this also actually resizes the physical terminal
+    /// window, but the behavior is implementation defined. You generally don't
+    /// need to call this yourself, since `get_input` will call it for you when
+    /// `auto_resize` is set and a resize event comes in. If you set
+    /// `auto_resize` to `false` you must call `resize` yourself whenever the
+    /// terminal changes size, otherwise the window will keep believing it's the
+    /// old size and drawing will be inconsistent with what's actually on
+    /// screen.
+    pub fn resize(&mut self, rows: i32, cols: i32) -> bool {
+        to_bool(pancurses::resize_term(rows, cols))
+    }
 
     /// Checks if the current terminal supports the use of colors.
     pub fn is_color_terminal(&mut self) -> bool {
         self.color_support
     }
 
+    /// Checks if the current terminal supports redefining the RGB values
+    /// associated with a `Color`, via `set_color_definition`.
+    pub fn can_change_colors(&mut self) -> bool {
+        pancurses::can_change_color()
+    }
+
+    /// Redefines the RGB values associated with a `Color`. Each of `r`, `g`,
+    /// and `b` must be in the range 0 to 1000 (inclusive); values outside that
+    /// range are rejected and `false` is returned. Also returns `false` if
+    /// `can_change_colors` is `false`.
+    ///
+    /// Note that this changes the color for the whole terminal: every cell
+    /// already on screen that uses this `Color` as its foreground or
+    /// background, not just future output, will be redrawn in the new RGB
+    /// value.
+    pub fn set_color_definition(&mut self, color: Color, r: i16, g: i16, b: i16) -> bool {
+        if !self.can_change_colors() {
+            return false;
+        }
+        if r < 0 || r > 1000 || g < 0 || g > 1000 || b < 0 || b > 1000 {
+            return false;
+        }
+        to_bool(pancurses::init_color(color_to_i16(color), r, g, b))
+    }
+
+    /// Gets the RGB values currently associated with a `Color`, each in the
+    /// range 0 to 1000 (inclusive). Returns `None` if `can_change_colors` is
+    /// `false`.
+    pub fn get_color_definition(&mut self, color: Color) -> Option<(i16, i16, i16)> {
+        if !self.can_change_colors() {
+            return None;
+        }
+        Some(pancurses::color_content(color_to_i16(color)))
+    }
+
     /// Sets the current color pair of the window. Output at any location will
     /// use this pair until a new pair is set. Does nothing if the terminal does
     /// not support colors in the first place.
@@ -335,6 +539,47 @@ impl EasyCurses {
         }
     }
 
+    /// Reads back the foreground/background pair currently set on the
+    /// window, inverting the encoding used by `ColorPair::from`. Returns
+    /// `None` if the terminal doesn't support colors, or if the pair
+    /// currently active doesn't decode to one of our eight named colors on
+    /// each side (for example if something outside easycurses set a raw pair
+    /// id directly).
+    pub fn get_color_pair(&mut self) -> Option<(Color, Color)> {
+        if !self.color_support {
+            return None;
+        }
+        let (_attrs, pair_id) = self.win.attrget();
+        if pair_id == 0 {
+            return None;
+        }
+        let (fg, bg) = ColorPair::fgbg_from_pairid(pair_id);
+        match (i16_to_color(fg), i16_to_color(bg)) {
+            (Some(fg_color), Some(bg_color)) => Some((fg_color, bg_color)),
+            _ => None,
+        }
+    }
+
+    /// Reads back which of the text attributes easycurses knows about are
+    /// currently active on the window. Useful for saving the current style
+    /// before drawing something else, then restoring it with
+    /// `set_attributes`.
+    ///
+    /// Unlike `get_color_pair`, this is unaffected by `is_color_terminal`:
+    /// bold/underline/reverse/blink/dim are plain curses text attributes, not
+    /// color pairs, so they're read back the same way whether or not the
+    /// terminal supports color.
+    pub fn get_attributes(&mut self) -> Attributes {
+        let (attrs, _pair_id) = self.win.attrget();
+        Attributes {
+            bold: attrs & pancurses::A_BOLD != 0,
+            underline: attrs & pancurses::A_UNDERLINE != 0,
+            reverse: attrs & pancurses::A_REVERSE != 0,
+            blink: attrs & pancurses::A_BLINK != 0,
+            dim: attrs & pancurses::A_DIM != 0,
+        }
+    }
+
     /// Enables or disables bold text for all future input. The bool is if the
     /// operation was successful or not.
     pub fn set_bold(&mut self, bold_on: bool) -> bool {
@@ -355,6 +600,50 @@ impl EasyCurses {
         })
     }
 
+    /// Enables or disables reverse video (foreground and background swapped)
+    /// for all future output. The bool is if the operation was successful or
+    /// not.
+    pub fn set_reverse(&mut self, reverse_on: bool) -> bool {
+        to_bool(if reverse_on {
+            self.win.attron(pancurses::Attribute::Reverse)
+        } else {
+            self.win.attroff(pancurses::Attribute::Reverse)
+        })
+    }
+
+    /// Enables or disables blinking text for all future output. The bool is
+    /// if the operation was successful or not.
+    pub fn set_blink(&mut self, blink_on: bool) -> bool {
+        to_bool(if blink_on {
+            self.win.attron(pancurses::Attribute::Blink)
+        } else {
+            self.win.attroff(pancurses::Attribute::Blink)
+        })
+    }
+
+    /// Enables or disables dim (half-bright) text for all future output. The
+    /// bool is if the operation was successful or not.
+    pub fn set_dim(&mut self, dim_on: bool) -> bool {
+        to_bool(if dim_on {
+            self.win.attron(pancurses::Attribute::Dim)
+        } else {
+            self.win.attroff(pancurses::Attribute::Dim)
+        })
+    }
+
+    /// Applies a whole set of text attributes at once, so callers can switch
+    /// between named styles without a sequence of individual toggles. Equally
+    /// useful for restoring an `Attributes` value saved earlier from
+    /// `get_attributes`. Returns `true` only if every individual attribute was
+    /// applied successfully.
+    pub fn set_attributes(&mut self, attrs: Attributes) -> bool {
+        self.set_bold(attrs.bold)
+            & self.set_underline(attrs.underline)
+            & self.set_reverse(attrs.reverse)
+            & self.set_blink(attrs.blink)
+            & self.set_dim(attrs.dim)
+    }
+
     /// Returns the number of rows and columns available in the window.
     pub fn get_row_col_count(&mut self) -> (i32, i32) {
         self.win.get_max_yx()
@@ -429,6 +718,22 @@ impl EasyCurses {
         to_bool(self.win.refresh())
     }
 
+    /// Copies the window's changes into curses' pending update buffer without
+    /// touching the physical terminal, via `noutrefresh`. Use this instead of
+    /// `refresh` when you want to mark several logically separate updates and
+    /// then commit them all at once with `doupdate`, which avoids the cost of
+    /// a full physical screen update per region.
+    pub fn queue_refresh(&mut self) -> bool {
+        to_bool(self.win.noutrefresh())
+    }
+
+    /// Flushes all changes queued up by `queue_refresh` to the physical
+    /// terminal in one optimized pass, via `pancurses::doupdate`. `refresh` is
+    /// equivalent to calling `queue_refresh` followed by `doupdate`.
+    pub fn doupdate(&mut self) -> bool {
+        to_bool(pancurses::doupdate())
+    }
+
     /// Plays an audible beep if possible, if not the screen is flashed. If
     /// neither is available then nothing happens.
     pub fn beep(&mut self) {
@@ -450,8 +755,25 @@ impl EasyCurses {
     }
 
     /// Gets an `Input` from the curses input buffer. Depending on the `timeout` setting that y
+    ///
+    /// If `auto_resize` is set (the default) and the input is
+    /// `Input::KeyResize`, this first calls `resize_term(0, 0)` to have the
+    /// window re-read the real terminal dimensions, then `clear` and
+    /// `refresh` to get the screen back into a consistent state, before
+    /// handing the event back to you. If you set `auto_resize` to `false` you
+    /// are responsible for calling `resize` (or otherwise reacting to
+    /// `Input::KeyResize`) yourself; until you do, `get_row_col_count` and all
+    /// drawing will be out of sync with the real terminal size.
     pub fn get_input(&mut self) -> Option<pancurses::Input> {
-        self.win.getch()
+        let input = self.win.getch();
+        if self.auto_resize {
+            if let Some(pancurses::Input::KeyResize) = input {
+                pancurses::resize_term(0, 0);
+                self.win.clear();
+                self.win.refresh();
+            }
+        }
+        input
     }
 
     /// Discards all type-ahead that has been input by the user but not yet read
@@ -460,6 +782,35 @@ impl EasyCurses {
         pancurses::flushinp();
     }
 
+    /// Sets which mouse events should be reported, returning the mask that
+    /// was previously active. `get_input` will start returning
+    /// `Input::KeyMouse` for the events you select; call `get_mouse_event`
+    /// immediately after to decode them.
+    pub fn set_mouse_mask(&mut self, mask: MouseMask) -> MouseMask {
+        let mut old_mask: pancurses::mmask_t = 0;
+        pancurses::mousemask(mask.0, Some(&mut old_mask));
+        MouseMask(old_mask)
+    }
+
+    /// After `get_input` returns `Input::KeyMouse`, call this to decode the
+    /// event that triggered it. Returns `None` if there was no mouse event
+    /// available to read.
+    ///
+    /// You must call this exactly once for every `Input::KeyMouse` you
+    /// receive. If you don't call it, or call it more than once, the mouse
+    /// event queue gets out of sync with the rest of curses input.
+    pub fn get_mouse_event(&mut self) -> Option<MouseEvent> {
+        match pancurses::getmouse() {
+            Ok(mevent) => Some(MouseEvent {
+                x: mevent.x,
+                y: mevent.y,
+                z: mevent.z,
+                button_state: mevent.bstate,
+            }),
+            Err(_) => None,
+        }
+    }
+
     /// Pushes an `Input` value into the input stack so that it will be returned
     /// by the next call to `get_input`.
     pub fn un_get_input(&mut self, input: &pancurses::Input) -> bool {